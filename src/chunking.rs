@@ -0,0 +1,118 @@
+/// A contiguous slice of the original input, sized to fit under an
+/// embedding model's token budget.
+pub struct Chunk {
+    pub text: String,
+    pub start_char: usize,
+    pub end_char: usize,
+}
+
+/// Splits `text` into overlapping windows of at most `max_tokens` tokens,
+/// advancing by `max_tokens - overlap_tokens` tokens each step, so that
+/// context isn't lost at a window boundary.
+///
+/// Token counts are approximated by whitespace-separated words rather than
+/// a model-specific tokenizer - close enough to keep chunks under budget
+/// without pulling in a full tokenizer dependency.
+pub fn chunk_text(text: &str, max_tokens: usize, overlap_tokens: usize) -> Vec<Chunk> {
+    assert!(max_tokens > 0, "max_tokens must be greater than zero");
+    assert!(
+        overlap_tokens < max_tokens,
+        "overlap_tokens must be smaller than max_tokens"
+    );
+
+    let words = word_spans(text);
+
+    if words.is_empty() {
+        return Vec::new();
+    }
+
+    let stride = max_tokens - overlap_tokens;
+
+    let mut chunks = Vec::new();
+    let mut index = 0;
+
+    loop {
+        let end_index = (index + max_tokens).min(words.len());
+
+        let start_char = words[index].0;
+        let end_char = words[end_index - 1].1;
+
+        chunks.push(Chunk {
+            text: text[start_char..end_char].to_string(),
+            start_char,
+            end_char,
+        });
+
+        if end_index == words.len() {
+            break;
+        }
+
+        index += stride;
+    }
+
+    chunks
+}
+
+/// Byte ranges of whitespace-separated words in `text`, used as the token
+/// approximation for [`chunk_text`].
+fn word_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start: Option<usize> = None;
+
+    for (index, ch) in text.char_indices() {
+        if ch.is_whitespace() {
+            if let Some(word_start) = start.take() {
+                spans.push((word_start, index));
+            }
+        } else if start.is_none() {
+            start = Some(index);
+        }
+    }
+
+    if let Some(word_start) = start {
+        spans.push((word_start, text.len()));
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_spans_splits_on_whitespace() {
+        assert_eq!(word_spans("one  two\tthree"), vec![(0, 3), (5, 8), (9, 14)]);
+        assert_eq!(word_spans(""), Vec::<(usize, usize)>::new());
+        assert_eq!(word_spans("   "), Vec::<(usize, usize)>::new());
+    }
+
+    #[test]
+    fn chunk_text_returns_nothing_for_empty_input() {
+        assert!(chunk_text("", 4, 1).is_empty());
+        assert!(chunk_text("   ", 4, 1).is_empty());
+    }
+
+    #[test]
+    fn chunk_text_fits_short_input_in_one_chunk() {
+        let chunks = chunk_text("one two three", 4, 1);
+
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].text, "one two three");
+    }
+
+    #[test]
+    fn chunk_text_overlaps_windows_by_overlap_tokens() {
+        let chunks = chunk_text("a b c d e f g", 4, 1);
+
+        let texts: Vec<&str> = chunks.iter().map(|chunk| chunk.text.as_str()).collect();
+
+        assert_eq!(texts, vec!["a b c d", "d e f g"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "overlap_tokens must be smaller than max_tokens")]
+    fn chunk_text_rejects_overlap_not_smaller_than_max() {
+        chunk_text("a b c", 2, 2);
+    }
+}
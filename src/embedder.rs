@@ -0,0 +1,178 @@
+use async_trait::async_trait;
+use openai_dive::v1::{
+    api::Client,
+    models::EmbeddingsEngine,
+    resources::embedding::{EmbeddingInput, EmbeddingOutput, EmbeddingParameters},
+};
+use serde::{Deserialize, Serialize};
+use std::env;
+
+/// Output produced by an [`Embedder`], matching the shape of the input.
+pub enum EmbeddingOutputType {
+    Single(Vec<f64>),
+    Multiple(Vec<Vec<f64>>),
+}
+
+/// A backend capable of turning text into embedding vectors. `provider`
+/// and `model` get persisted alongside generated embeddings so vectors
+/// from different backends are never compared against each other.
+#[async_trait]
+pub trait Embedder {
+    async fn embed(&self, inputs: Vec<String>) -> EmbeddingOutputType;
+
+    /// Stable identifier persisted alongside generated embeddings so a
+    /// dictionary can be matched back to the embedder that produced it.
+    fn provider(&self) -> &'static str;
+
+    /// The model name, persisted for the same reason as `provider`.
+    fn model(&self) -> String;
+}
+
+/// Embeds via the OpenAI API.
+pub struct OpenAiEmbedder {
+    client: Client,
+    model: EmbeddingsEngine,
+}
+
+impl OpenAiEmbedder {
+    pub fn new() -> Self {
+        let api_key = env::var("OPENAI_API_KEY").expect("$OPENAI_API_KEY is not set");
+
+        Self {
+            client: Client::new(api_key),
+            model: EmbeddingsEngine::TextEmbedding3Small,
+        }
+    }
+}
+
+#[async_trait]
+impl Embedder for OpenAiEmbedder {
+    async fn embed(&self, inputs: Vec<String>) -> EmbeddingOutputType {
+        let parameters = EmbeddingParameters {
+            model: self.model.to_string(),
+            input: EmbeddingInput::StringArray(inputs),
+            encoding_format: None,
+            dimensions: None,
+            user: None,
+        };
+
+        let embedding_response = self.client.embeddings().create(parameters).await.unwrap();
+
+        let mut data = embedding_response.data;
+
+        match data.len() {
+            1 => EmbeddingOutputType::Single(into_floats(data.remove(0).embedding)),
+            _ => EmbeddingOutputType::Multiple(data.into_iter().map(|item| into_floats(item.embedding)).collect()),
+        }
+    }
+
+    fn provider(&self) -> &'static str {
+        "openai"
+    }
+
+    fn model(&self) -> String {
+        self.model.to_string()
+    }
+}
+
+/// OpenAI returns base64-encoded embeddings only when `encoding_format` is
+/// explicitly set to `base64`, which we never do, so `Float` is the only
+/// variant we should ever see in practice.
+fn into_floats(output: EmbeddingOutput) -> Vec<f64> {
+    match output {
+        EmbeddingOutput::Float(values) => values,
+        EmbeddingOutput::Base64(_) => panic!("Expected float embeddings, got base64-encoded output"),
+    }
+}
+
+#[derive(Serialize)]
+struct OllamaEmbeddingRequest<'a> {
+    model: &'a str,
+    prompt: &'a str,
+}
+
+#[derive(Deserialize)]
+struct OllamaEmbeddingResponse {
+    embedding: Vec<f64>,
+}
+
+/// Embeds via a locally running Ollama server, e.g. `ollama serve`.
+pub struct OllamaEmbedder {
+    base_url: String,
+    model: String,
+    client: reqwest::Client,
+}
+
+impl OllamaEmbedder {
+    pub fn new(model: String) -> Self {
+        let base_url =
+            env::var("OLLAMA_BASE_URL").unwrap_or_else(|_| "http://localhost:11434".to_string());
+
+        Self {
+            base_url,
+            model,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Embedder for OllamaEmbedder {
+    async fn embed(&self, prompts: Vec<String>) -> EmbeddingOutputType {
+        let url = format!("{}/api/embeddings", self.base_url);
+
+        let mut embeddings = Vec::with_capacity(prompts.len());
+
+        for prompt in &prompts {
+            let request = OllamaEmbeddingRequest {
+                model: &self.model,
+                prompt,
+            };
+
+            let response = self
+                .client
+                .post(&url)
+                .json(&request)
+                .send()
+                .await
+                .unwrap()
+                .json::<OllamaEmbeddingResponse>()
+                .await
+                .unwrap();
+
+            embeddings.push(response.embedding);
+        }
+
+        match embeddings.len() {
+            1 => EmbeddingOutputType::Single(embeddings.remove(0)),
+            _ => EmbeddingOutputType::Multiple(embeddings),
+        }
+    }
+
+    fn provider(&self) -> &'static str {
+        "ollama"
+    }
+
+    fn model(&self) -> String {
+        self.model.clone()
+    }
+}
+
+/// Builds the embedder selected via `EMBEDDER_PROVIDER` (`openai` by default,
+/// or `ollama`). For `ollama` the model is taken from `EMBEDDER_MODEL`
+/// (`nomic-embed-text` by default); the `openai` backend always uses
+/// `text-embedding-3-small` and does not read `EMBEDDER_MODEL`.
+pub fn build_embedder() -> Box<dyn Embedder> {
+    let provider = env::var("EMBEDDER_PROVIDER").unwrap_or_else(|_| "openai".to_string());
+
+    match provider.as_str() {
+        "openai" => Box::new(OpenAiEmbedder::new()),
+        "ollama" => {
+            let model =
+                env::var("EMBEDDER_MODEL").unwrap_or_else(|_| "nomic-embed-text".to_string());
+
+            Box::new(OllamaEmbedder::new(model))
+        }
+        other => panic!("Unknown $EMBEDDER_PROVIDER `{other}`, expected `openai` or `ollama`."),
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+const DEFAULT_LEXICON_PATH: &str = "data/lexicon.json";
+
+/// Per-label trigger words/phrases used to blend keyword matches into the
+/// embedding-based score - see [`crate::parse_args`]'s `--semantic-ratio`.
+pub struct Lexicon {
+    triggers: HashMap<String, Vec<String>>,
+}
+
+impl Lexicon {
+    /// Loads trigger words from `data/lexicon.json`, or `$LEXICON_PATH` if
+    /// set. Falls back to an empty lexicon - every keyword score is then
+    /// `0.0`, which is harmless unless `--semantic-ratio` is below `1.0`.
+    pub fn load() -> Self {
+        let path = std::env::var("LEXICON_PATH").unwrap_or_else(|_| DEFAULT_LEXICON_PATH.to_string());
+
+        let Ok(data) = std::fs::read_to_string(&path) else {
+            return Self {
+                triggers: HashMap::new(),
+            };
+        };
+
+        let raw: HashMap<String, Vec<String>> =
+            serde_json::from_str(&data).unwrap_or_else(|error| panic!("Failed to parse `{path}`: {error}"));
+
+        let triggers = raw
+            .into_iter()
+            .map(|(label, words)| (label, words.into_iter().map(|word| word.to_lowercase()).collect()))
+            .collect();
+
+        Self { triggers }
+    }
+
+    /// Normalized term-frequency of `label`'s trigger words/phrases found
+    /// in `tokens`, in `[0, 1]`.
+    pub fn keyword_score(&self, label: &str, tokens: &[String]) -> f64 {
+        if tokens.is_empty() {
+            return 0.0;
+        }
+
+        let Some(triggers) = self.triggers.get(label) else {
+            return 0.0;
+        };
+
+        let matches: usize = triggers
+            .iter()
+            .map(|trigger| {
+                let words = tokenize(trigger);
+
+                if words.is_empty() {
+                    return 0;
+                }
+
+                tokens.windows(words.len()).filter(|window| *window == words.as_slice()).count()
+            })
+            .sum();
+
+        (matches as f64 / tokens.len() as f64).min(1.0)
+    }
+}
+
+/// Splits `text` on whitespace, lowercasing each word and stripping its
+/// leading and trailing punctuation (inner punctuation such as the
+/// apostrophe in "i'm" is kept).
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|word| word.trim_matches(|ch: char| !ch.is_alphanumeric()).to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_lowercases_and_strips_punctuation() {
+        assert_eq!(tokenize("I'm FURIOUS!! Right now."), vec!["i'm", "furious", "right", "now"]);
+        assert_eq!(tokenize(""), Vec::<String>::new());
+    }
+
+    #[test]
+    fn keyword_score_is_normalized_match_count() {
+        let lexicon = Lexicon {
+            triggers: HashMap::from([("anger".to_string(), vec!["furious".to_string(), "livid".to_string()])]),
+        };
+
+        let tokens = tokenize("i am furious and livid today");
+
+        assert_eq!(lexicon.keyword_score("anger", &tokens), 2.0 / 6.0);
+    }
+
+    #[test]
+    fn keyword_score_is_zero_for_unknown_label_or_empty_tokens() {
+        let lexicon = Lexicon {
+            triggers: HashMap::from([("anger".to_string(), vec!["furious".to_string()])]),
+        };
+
+        assert_eq!(lexicon.keyword_score("happiness", &tokenize("furious")), 0.0);
+        assert_eq!(lexicon.keyword_score("anger", &[]), 0.0);
+    }
+}
@@ -1,98 +1,265 @@
+mod chunking;
+mod embedder;
+mod lexicon;
+mod taxonomy;
+
+use chunking::{chunk_text, Chunk};
 use colored::Colorize;
-use openai_dive::v1::{
-    api::Client,
-    models::EmbeddingsEngine,
-    resources::embedding::{EmbeddingInput, EmbeddingParameters},
-};
+use embedder::{build_embedder, Embedder, EmbeddingOutputType};
+use lexicon::{tokenize, Lexicon};
 use serde::Serialize;
-use std::str::FromStr;
+use std::path::Path;
 use std::{env, fs::File};
-use std::{fmt::Display, io::Read};
+use std::io::Read;
+use taxonomy::Taxonomy;
 
 const THRESHOLD: f64 = 70.0;
 
+const DEFAULT_CHUNK_MAX_TOKENS: usize = 512;
+const DEFAULT_CHUNK_OVERLAP_TOKENS: usize = 64;
+const DEFAULT_SEMANTIC_RATIO: f64 = 1.0;
+
 #[tokio::main]
 async fn main() {
-    let input = env::args()
-        .nth(1)
-        .expect("Please provide a word or sentence to analyze. Use the command `generate` to generate the embeddings for the sentiments.");
+    let args = parse_args();
+
+    let embedder = build_embedder();
+    let taxonomy = Taxonomy::load();
 
-    if input == "generate" {
-        process_generate_command().await;
+    if args.input == "generate" {
+        process_generate_command(embedder.as_ref(), &taxonomy).await;
 
         return;
     }
 
-    let embedding = generate_embedding(EmbeddingInputType::String(input.clone())).await;
+    let text = read_input(&args.input);
+
+    let chunks = chunk_text(&text, chunk_max_tokens(), chunk_overlap_tokens());
+
+    if chunks.is_empty() {
+        eprintln!("{}", "Nothing to analyze - input is empty.".red());
+        std::process::exit(1);
+    }
 
-    let embedding = match embedding {
-        EmbeddingOutputType::Single(embedding) => embedding,
-        _ => panic!("Expected single embedding."),
+    let chunk_texts = chunks.iter().map(|chunk| chunk.text.clone()).collect();
+
+    let embeddings = embedder.embed(chunk_texts).await;
+
+    let embeddings = into_vecs(embeddings);
+
+    let emotions = match get_emotions(embedder.as_ref()).await {
+        Ok(emotions) => emotions,
+        Err(error) => {
+            eprintln!("{}", error.red());
+            std::process::exit(1);
+        }
     };
 
-    let mut max_similarity = 0.0;
+    let lexicon = Lexicon::load();
+
+    // Per-chunk, per-label percentage score, blending cosine similarity
+    // with the lexicon's keyword score according to `args.semantic_ratio`.
+    let mut chunk_scores = Vec::<Vec<(String, f64)>>::new();
 
-    let mut similiarity_dictonary = Vec::<(Sentiment, f64)>::new();
+    for (chunk, embedding) in chunks.iter().zip(embeddings.iter()) {
+        let embedding = normalize(embedding);
+        let tokens = tokenize(&chunk.text);
 
-    let emotions = get_emotions().await;
+        let mut scores = Vec::<(String, f64)>::new();
 
-    for (_index, item) in emotions.iter().enumerate() {
-        let dot_product = calculate_dot_product(&embedding, &item.embedding).await;
+        for item in &emotions {
+            let cosine_similarity = match calculate_dot_product(&embedding, &item.embedding).await {
+                Ok(cosine_similarity) => cosine_similarity,
+                Err(error) => {
+                    eprintln!("{}", error.red());
+                    std::process::exit(1);
+                }
+            };
 
-        similiarity_dictonary.push((item.sentiment.clone(), dot_product));
+            let cosine_unit = (cosine_similarity + 1.0) / 2.0;
+            let keyword_score = lexicon.keyword_score(&item.label, &tokens);
 
-        if max_similarity < dot_product {
-            max_similarity = dot_product;
+            let blended = args.semantic_ratio * cosine_unit + (1.0 - args.semantic_ratio) * keyword_score;
+
+            scores.push((item.label.clone(), blended * 100.0));
         }
+
+        chunk_scores.push(scores);
     }
 
-    println!("Input: {}", input.bright_blue().bold().underline());
+    println!("Input: {}", args.input.bright_blue().bold().underline());
+    println!(
+        "Split into {} chunk{}",
+        chunks.len(),
+        if chunks.len() == 1 { "" } else { "s" }
+    );
 
-    let mut similiarity_dictonary: Vec<(Sentiment, f64)> = similiarity_dictonary
+    print_document_profile(&taxonomy, &emotions, &chunk_scores);
+    print_chunk_breakdown(&taxonomy, &chunks, &chunk_scores);
+}
+
+/// Aggregates each label's mean and max percentage across all chunks and
+/// prints the resulting document-level profile.
+fn print_document_profile(taxonomy: &Taxonomy, emotions: &[Item], chunk_scores: &[Vec<(String, f64)>]) {
+    let mut profile: Vec<(String, f64, f64)> = emotions
         .iter()
-        .map(|(sentiment, dot_product)| (sentiment.clone(), 100.0 * (dot_product / max_similarity)))
+        .enumerate()
+        .map(|(label_index, item)| {
+            let values: Vec<f64> = chunk_scores.iter().map(|scores| scores[label_index].1).collect();
+
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            let max = values.iter().cloned().fold(f64::MIN, f64::max);
+
+            (item.label.clone(), mean, max)
+        })
         .collect();
 
-    similiarity_dictonary.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+    profile.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+
+    println!("\n{}", "Document sentiment profile".bold());
 
-    similiarity_dictonary.iter().for_each(|(sentiment, similarity)| {
-        if similarity < &THRESHOLD {
-            println!("{:<12} {}", sentiment.to_string(), format!("{:.2}%", similarity).red());
+    profile.iter().for_each(|(label, mean, max)| {
+        let line = format!("{:<20} mean {:>6.2}%  max {:>6.2}%", format_label(taxonomy, label), mean, max);
+
+        if *mean < THRESHOLD {
+            println!("{}", line.red());
         } else {
-            println!(
-                "{:<12} {}",
-                sentiment.to_string(),
-                format!("{:.2}%", similarity).green()
-            );
+            println!("{}", line.green());
         }
     });
 }
 
-async fn process_generate_command() {
-    let sentiments = vec![
-        Sentiment::Sadness,
-        Sentiment::Happiness,
-        Sentiment::Fear,
-        Sentiment::Anger,
-        Sentiment::Suprise,
-        Sentiment::Disgust,
-    ];
+/// Prints each chunk's text range alongside its strongest label.
+fn print_chunk_breakdown(taxonomy: &Taxonomy, chunks: &[Chunk], chunk_scores: &[Vec<(String, f64)>]) {
+    println!("\n{}", "Per-chunk breakdown".bold());
+
+    chunks.iter().zip(chunk_scores.iter()).enumerate().for_each(|(index, (chunk, scores))| {
+        let mut scores = scores.clone();
+        scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+
+        let (top_label, top_score) = &scores[0];
+
+        println!(
+            "[{:>3}] ({:>5}..{:<5}) {:<20} {:.2}%  {}",
+            index,
+            chunk.start_char,
+            chunk.end_char,
+            format_label(taxonomy, top_label),
+            top_score,
+            truncate(&chunk.text, 60).dimmed()
+        );
+    });
+}
+
+/// Renders a label's name as `<emoji> <display_name>`, falling back to the
+/// bare name if it's no longer present in the taxonomy (e.g. the config
+/// file changed after `generate` ran).
+fn format_label(taxonomy: &Taxonomy, name: &str) -> String {
+    match taxonomy.find(name) {
+        Some(label) => format!("{} {}", label.emoji, label.display_name),
+        None => name.to_string(),
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
 
-    let mut items = Vec::<Item>::new();
+    if collapsed.chars().count() <= max_chars {
+        return collapsed;
+    }
+
+    format!("{}…", collapsed.chars().take(max_chars).collect::<String>())
+}
+
+/// Reads the text to analyze either from a file path, if `input` names an
+/// existing file, or from `input` itself.
+fn read_input(input: &str) -> String {
+    let path = Path::new(input);
+
+    if path.is_file() {
+        std::fs::read_to_string(path).unwrap_or_else(|error| {
+            eprintln!("{}", format!("Failed to read `{input}`: {error}").red());
+            std::process::exit(1);
+        })
+    } else {
+        input.to_string()
+    }
+}
+
+/// Parsed CLI invocation: the word/sentence/file to analyze (or
+/// `"generate"`), plus the `--semantic-ratio` weight.
+struct Args {
+    input: String,
+    semantic_ratio: f64,
+}
+
+/// Parses `--semantic-ratio <weight>` out of the CLI args; everything else
+/// is treated positionally as the input. `1.0` (pure embeddings) is the
+/// default, matching the tool's existing behavior.
+fn parse_args() -> Args {
+    let mut input = None;
+    let mut semantic_ratio = DEFAULT_SEMANTIC_RATIO;
+
+    let mut raw_args = env::args().skip(1);
+
+    while let Some(arg) = raw_args.next() {
+        if arg == "--semantic-ratio" {
+            let value = raw_args.next().expect("--semantic-ratio requires a value");
+
+            semantic_ratio = value
+                .parse()
+                .unwrap_or_else(|_| panic!("--semantic-ratio must be a number, got `{value}`"));
+        } else if input.is_none() {
+            input = Some(arg);
+        }
+    }
+
+    assert!(
+        (0.0..=1.0).contains(&semantic_ratio),
+        "--semantic-ratio must be between 0 and 1, got {semantic_ratio}"
+    );
+
+    let input = input.expect("Please provide a word or sentence to analyze. Use the command `generate` to generate the embeddings for the sentiments.");
 
-    let text_sentiments = sentiments.iter().map(|sentiment| sentiment.to_string()).collect();
+    Args { input, semantic_ratio }
+}
 
-    let embeddings = generate_embedding(EmbeddingInputType::Array(text_sentiments)).await;
+fn chunk_max_tokens() -> usize {
+    env::var("CHUNK_MAX_TOKENS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_CHUNK_MAX_TOKENS)
+}
 
-    let embeddings = match embeddings {
+fn chunk_overlap_tokens() -> usize {
+    env::var("CHUNK_OVERLAP_TOKENS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_CHUNK_OVERLAP_TOKENS)
+}
+
+fn into_vecs(output: EmbeddingOutputType) -> Vec<Vec<f64>> {
+    match output {
+        EmbeddingOutputType::Single(embedding) => vec![embedding],
         EmbeddingOutputType::Multiple(embeddings) => embeddings,
-        _ => panic!("Expected multiple embeddings"),
-    };
+    }
+}
 
-    for (index, sentiment) in sentiments.iter().enumerate() {
+async fn process_generate_command(embedder: &dyn Embedder, taxonomy: &Taxonomy) {
+    let mut items = Vec::<Item>::new();
+
+    let prompts = taxonomy.labels.iter().map(|label| label.render_prompt()).collect();
+
+    let embeddings = embedder.embed(prompts).await;
+
+    let embeddings = into_vecs(embeddings);
+
+    for (index, label) in taxonomy.labels.iter().enumerate() {
         items.push(Item {
-            sentiment: sentiment.clone(),
-            embedding: embeddings[index].clone(),
+            label: label.name.clone(),
+            provider: embedder.provider().to_string(),
+            model: embedder.model(),
+            embedding: normalize(&embeddings[index]),
         });
     }
 
@@ -103,7 +270,9 @@ async fn process_generate_command() {
     std::fs::write(file_path, json).unwrap();
 }
 
-async fn get_emotions() -> Vec<Item> {
+/// Loads the generated dictionary, refusing to return vectors that were
+/// produced by a different embedder than the one currently active.
+async fn get_emotions(embedder: &dyn Embedder) -> Result<Vec<Item>, String> {
     let file_path = "data/embedded-emotions.json";
 
     let mut file = File::open(file_path).unwrap();
@@ -118,113 +287,107 @@ async fn get_emotions() -> Vec<Item> {
         .unwrap()
         .iter()
         .map(|item| Item {
-            sentiment: Sentiment::from_str(item["sentiment"].as_str().unwrap()).unwrap(),
-            embedding: item["embedding"]
-                .as_array()
-                .unwrap()
-                .iter()
-                .map(|value| value.as_f64().unwrap())
-                .collect(),
+            label: item["label"].as_str().unwrap().to_string(),
+            provider: item["provider"].as_str().unwrap().to_string(),
+            model: item["model"].as_str().unwrap().to_string(),
+            embedding: normalize(
+                &item["embedding"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|value| value.as_f64().unwrap())
+                    .collect::<Vec<f64>>(),
+            ),
         })
         .collect();
 
-    items
-}
-
-enum EmbeddingInputType {
-    String(String),
-    Array(Vec<String>),
-}
+    if let Some(mismatch) = items
+        .iter()
+        .find(|item| item.provider != embedder.provider() || item.model != embedder.model())
+    {
+        return Err(format!(
+            "data/embedded-emotions.json was generated with {}/{}, but the active embedder is {}/{}. Re-run `generate` with the matching embedder.",
+            mismatch.provider,
+            mismatch.model,
+            embedder.provider(),
+            embedder.model()
+        ));
+    }
 
-enum EmbeddingOutputType {
-    Single(Vec<f64>),
-    Multiple(Vec<Vec<f64>>),
+    Ok(items)
 }
 
-async fn generate_embedding(input: EmbeddingInputType) -> EmbeddingOutputType {
-    let api_key = env::var("OPENAI_API_KEY").expect("$OPENAI_API_KEY is not set");
-
-    let client = Client::new(api_key);
-
-    let formatted_input = match input {
-        EmbeddingInputType::String(input) => EmbeddingInput::String(input),
-        EmbeddingInputType::Array(input) => EmbeddingInput::StringArray(input),
-    };
-
-    let parameters = EmbeddingParameters {
-        model: EmbeddingsEngine::TextEmbedding3Small.to_string(),
-        input: formatted_input,
-        encoding_format: None,
-        dimensions: None,
-        user: None,
-    };
-
-    let embedding_response = client.embeddings().create(parameters).await.unwrap();
-
-    match embedding_response.data.len() {
-        1 => EmbeddingOutputType::Single(embedding_response.data[0].embedding.clone()),
-        _ => EmbeddingOutputType::Multiple(
-            embedding_response
-                .data
-                .iter()
-                .map(|item| item.embedding.clone())
-                .collect(),
-        ),
+/// Dot product of two unit vectors, which is exactly their cosine
+/// similarity in `[-1, 1]`.
+async fn calculate_dot_product(embedding1: &[f64], embedding2: &[f64]) -> Result<f64, String> {
+    if embedding1.len() != embedding2.len() {
+        return Err(format!(
+            "Cannot compare embeddings of different dimensions ({} vs {}). Did you mix embedders?",
+            embedding1.len(),
+            embedding2.len()
+        ));
     }
-}
 
-async fn calculate_dot_product(embedding1: &Vec<f64>, embedding2: &Vec<f64>) -> f64 {
     let mut dot_product: f64 = 0.0;
 
     for (a, b) in embedding1.iter().zip(embedding2.iter()) {
         dot_product += a * b;
     }
 
-    dot_product
+    Ok(dot_product)
 }
 
-#[derive(Debug, Serialize, Clone)]
-#[serde(rename_all = "lowercase")]
-enum Sentiment {
-    Sadness,
-    Happiness,
-    Fear,
-    Anger,
-    Suprise,
-    Disgust,
+/// Scales `embedding` to unit length so that a dot product between two
+/// normalized vectors is their cosine similarity. Zero-norm vectors are
+/// returned unchanged to avoid dividing by zero.
+fn normalize(embedding: &[f64]) -> Vec<f64> {
+    let norm = embedding.iter().map(|value| value * value).sum::<f64>().sqrt();
+
+    if norm == 0.0 {
+        return embedding.to_vec();
+    }
+
+    embedding.iter().map(|value| value / norm).collect()
 }
 
 #[derive(Debug, Serialize)]
 struct Item {
-    sentiment: Sentiment,
+    label: String,
+    provider: String,
+    model: String,
     embedding: Vec<f64>,
 }
 
-impl Display for Sentiment {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Sentiment::Sadness => write!(f, "ðŸ˜” Sadness"),
-            Sentiment::Happiness => write!(f, "ðŸ˜„ Happiness"),
-            Sentiment::Fear => write!(f, "ðŸ˜¨ Fear"),
-            Sentiment::Anger => write!(f, "ðŸ˜  Anger"),
-            Sentiment::Suprise => write!(f, "ðŸ˜® Suprise"),
-            Sentiment::Disgust => write!(f, "ðŸ¤® Disgust"),
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_scales_to_unit_length() {
+        let normalized = normalize(&[3.0, 4.0]);
+
+        assert!((normalized[0] - 0.6).abs() < 1e-9);
+        assert!((normalized[1] - 0.8).abs() < 1e-9);
     }
-}
 
-impl FromStr for Sentiment {
-    type Err = ();
+    #[test]
+    fn normalize_leaves_zero_vector_unchanged() {
+        assert_eq!(normalize(&[0.0, 0.0]), vec![0.0, 0.0]);
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "sadness" => Ok(Sentiment::Sadness),
-            "happiness" => Ok(Sentiment::Happiness),
-            "fear" => Ok(Sentiment::Fear),
-            "anger" => Ok(Sentiment::Anger),
-            "suprise" => Ok(Sentiment::Suprise),
-            "disgust" => Ok(Sentiment::Disgust),
-            _ => Err(()),
-        }
+    #[tokio::test]
+    async fn calculate_dot_product_of_identical_unit_vectors_is_one() {
+        let embedding = normalize(&[1.0, 2.0, 3.0]);
+
+        let cosine_similarity = calculate_dot_product(&embedding, &embedding).await.unwrap();
+
+        assert!((cosine_similarity - 1.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn calculate_dot_product_rejects_mismatched_dimensions() {
+        let error = calculate_dot_product(&[1.0, 2.0], &[1.0, 2.0, 3.0]).await.unwrap_err();
+
+        assert!(error.contains("different dimensions"));
     }
 }
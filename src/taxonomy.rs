@@ -0,0 +1,115 @@
+use serde::Deserialize;
+use std::env;
+
+const DEFAULT_TAXONOMY_PATH: &str = "config/taxonomy.json";
+
+/// A single classification label: its canonical name, how to present it,
+/// and the template used to build the text embedded for it during
+/// `generate`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Label {
+    pub name: String,
+    pub display_name: String,
+    pub emoji: String,
+    #[serde(default = "default_prompt_template")]
+    pub prompt_template: String,
+}
+
+fn default_prompt_template() -> String {
+    "an expression of {label}".to_string()
+}
+
+impl Label {
+    /// Renders `prompt_template`, substituting `{label}` with `name`.
+    pub fn render_prompt(&self) -> String {
+        self.prompt_template.replace("{label}", &self.name)
+    }
+}
+
+/// The set of labels the tool classifies input against, loaded from
+/// `config/taxonomy.json` (or `$TAXONOMY_PATH`).
+pub struct Taxonomy {
+    pub labels: Vec<Label>,
+}
+
+impl Taxonomy {
+    /// Loads the taxonomy from disk, falling back to the built-in six
+    /// emotions so the tool still runs without a config file.
+    pub fn load() -> Self {
+        let path = env::var("TAXONOMY_PATH").unwrap_or_else(|_| DEFAULT_TAXONOMY_PATH.to_string());
+
+        let Ok(data) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+
+        let labels: Vec<Label> =
+            serde_json::from_str(&data).unwrap_or_else(|error| panic!("Failed to parse `{path}`: {error}"));
+
+        assert!(!labels.is_empty(), "`{path}` must define at least one label");
+
+        Self { labels }
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Label> {
+        self.labels.iter().find(|label| label.name == name)
+    }
+}
+
+impl Default for Taxonomy {
+    fn default() -> Self {
+        let defaults = [
+            ("sadness", "Sadness", "😔"),
+            ("happiness", "Happiness", "😄"),
+            ("fear", "Fear", "😨"),
+            ("anger", "Anger", "😠"),
+            ("suprise", "Suprise", "😮"),
+            ("disgust", "Disgust", "🤮"),
+        ];
+
+        let labels = defaults
+            .into_iter()
+            .map(|(name, display_name, emoji)| Label {
+                name: name.to_string(),
+                display_name: display_name.to_string(),
+                emoji: emoji.to_string(),
+                prompt_template: default_prompt_template(),
+            })
+            .collect();
+
+        Self { labels }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_prompt_substitutes_label_into_default_template() {
+        let label = Label {
+            name: "anger".to_string(),
+            display_name: "Anger".to_string(),
+            emoji: "😠".to_string(),
+            prompt_template: default_prompt_template(),
+        };
+
+        assert_eq!(label.render_prompt(), "an expression of anger");
+    }
+
+    #[test]
+    fn render_prompt_substitutes_label_into_custom_template() {
+        let label = Label {
+            name: "gratitude".to_string(),
+            display_name: "Gratitude".to_string(),
+            emoji: "🙏".to_string(),
+            prompt_template: "feeling deeply {label} about something".to_string(),
+        };
+
+        assert_eq!(label.render_prompt(), "feeling deeply gratitude about something");
+    }
+
+    #[test]
+    fn default_taxonomy_has_six_labels() {
+        assert_eq!(Taxonomy::default().labels.len(), 6);
+    }
+}